@@ -1,21 +1,31 @@
 use super::{Key, PrivKey, PubKey};
 use crate::error::Error;
 use crate::sshbuf::{SshReadExt, SshWriteExt};
-use openssl::bn::BigNumContext;
-use openssl::ec::{EcGroup, EcGroupRef, EcKey, EcKeyRef, EcPointRef, PointConversionForm};
-use openssl::hash::MessageDigest;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::derive::Deriver;
+use openssl::ec::{EcGroup, EcGroupRef, EcKey, EcKeyRef, EcPoint, EcPointRef, PointConversionForm};
+use openssl::ecdsa::EcdsaSig;
+use openssl::hash::{hash, MessageDigest};
 use openssl::nid::Nid;
 use openssl::pkey::{HasParams, HasPublic, PKey, Private, Public};
-use openssl::sign::{Signer, Verifier};
+use openssl::sign::Signer;
 use std::convert::TryInto;
 use std::fmt;
-use std::io::Cursor;
+use std::io::{Cursor, Read, Write};
 use std::str::FromStr;
 
 const NIST_P256_NAME: &'static str = "ecdsa-sha2-nistp256";
 const NIST_P384_NAME: &'static str = "ecdsa-sha2-nistp384";
 const NIST_P521_NAME: &'static str = "ecdsa-sha2-nistp521";
 
+const SSHSIG_MAGIC: &'static [u8] = b"SSHSIG";
+const SSHSIG_VERSION: u32 = 1;
+// The SSHSIG message pre-hash is independent of the signing curve; OpenSSH only
+// accepts "sha256" or "sha512" here.
+const SSHSIG_HASH_ALG: &'static str = "sha512";
+const SSHSIG_PEM_BEGIN: &'static str = "-----BEGIN SSH SIGNATURE-----";
+const SSHSIG_PEM_END: &'static str = "-----END SSH SIGNATURE-----";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EcCurve {
     Nistp256,
@@ -55,6 +65,41 @@ impl EcCurve {
             EcCurve::Nistp521 => "nistp521",
         }
     }
+
+    pub fn digest(&self) -> MessageDigest {
+        match self {
+            EcCurve::Nistp256 => MessageDigest::sha256(),
+            EcCurve::Nistp384 => MessageDigest::sha384(),
+            EcCurve::Nistp521 => MessageDigest::sha512(),
+        }
+    }
+
+    /// The JWK `crv` name (RFC 7518) for this curve.
+    pub fn jwk_name(&self) -> &'static str {
+        match self {
+            EcCurve::Nistp256 => "P-256",
+            EcCurve::Nistp384 => "P-384",
+            EcCurve::Nistp521 => "P-521",
+        }
+    }
+
+    /// The fixed byte width of an affine coordinate / private scalar on this curve.
+    pub fn coordinate_size(&self) -> usize {
+        match self {
+            EcCurve::Nistp256 => 32,
+            EcCurve::Nistp384 => 48,
+            EcCurve::Nistp521 => 66,
+        }
+    }
+
+    fn from_jwk_name(s: &str) -> Result<Self, Error> {
+        match s {
+            "P-256" => Ok(EcCurve::Nistp256),
+            "P-384" => Ok(EcCurve::Nistp384),
+            "P-521" => Ok(EcCurve::Nistp521),
+            _ => Err(Error::UnsupportedCurve),
+        }
+    }
 }
 
 impl FromStr for EcCurve {
@@ -110,6 +155,83 @@ impl EcDsaPublicKey {
             curve: curve,
         })
     }
+
+    /// Parse a public key from a SEC1 point encoding on `curve`, accepting both
+    /// the uncompressed (`0x04`) and compressed (`0x02`/`0x03`) forms.
+    pub fn from_point_bytes(curve: EcCurve, point: &[u8]) -> Result<Self, Error> {
+        let group: EcGroup = curve.try_into()?;
+        let mut bn_ctx = BigNumContext::new()?;
+        let point = EcPoint::from_bytes(&group, point, &mut bn_ctx)?;
+        Self::new(&group, &point)
+    }
+
+    /// Serialize the SSH public-key blob with an explicit SEC1 point form. The
+    /// default wire output ([`PubKey::blob`]) stays uncompressed for SSH
+    /// compatibility; this lets callers emit the shorter compressed encoding.
+    pub fn blob_with_form(&self, form: PointConversionForm) -> Result<Vec<u8>, Error> {
+        blob_with_form(self.curve, &self.key, form)
+    }
+
+    /// Serialize the public key as a JSON Web Key (RFC 7517/7518).
+    pub fn to_jwk(&self) -> Result<String, Error> {
+        let mut bn_ctx = BigNumContext::new()?;
+        let (x, y) = self.affine_coordinates(&mut bn_ctx)?;
+        Ok(jwk_json(self.curve, &x, &y, None))
+    }
+
+    /// Reconstruct a public key from its JSON Web Key representation.
+    pub fn from_jwk(jwk: &str) -> Result<Self, Error> {
+        let curve = jwk_curve(jwk)?;
+        let x = jwk_bignum(jwk, "x")?;
+        let y = jwk_bignum(jwk, "y")?;
+        let group: EcGroup = curve.try_into()?;
+        Ok(Self {
+            key: EcKey::from_public_key_affine_coordinates(&group, &x, &y)?,
+            curve,
+        })
+    }
+
+    /// Verify an armored `SSHSIG` detached signature produced for `namespace`
+    /// over `data`. Returns `false` when the namespace or signature do not match.
+    pub fn verify_sshsig(
+        &self,
+        data: &[u8],
+        namespace: &str,
+        armored: &str,
+    ) -> Result<bool, Error> {
+        let blob = dearmor(armored)?;
+        let mut reader = Cursor::new(blob);
+
+        let mut magic = [0u8; 6];
+        reader.read_exact(&mut magic)?;
+        if magic != SSHSIG_MAGIC {
+            return Err(Error::InvalidFormat);
+        }
+        if reader.read_uint32()? != SSHSIG_VERSION {
+            return Err(Error::InvalidFormat);
+        }
+        let _public_key = reader.read_string()?;
+        let sig_namespace = reader.read_string()?;
+        if sig_namespace != namespace.as_bytes() {
+            return Ok(false);
+        }
+        let _reserved = reader.read_string()?;
+        let hash_alg = reader.read_string()?;
+        let signature = reader.read_string()?;
+
+        let digest = hash(sshsig_digest(&hash_alg)?, data)?;
+        let signed = sshsig_signed_blob(&sig_namespace, &hash_alg, &digest)?;
+        self.verify(&signed, &signature)
+    }
+
+    fn affine_coordinates(&self, bn_ctx: &mut BigNumContext) -> Result<(BigNum, BigNum), Error> {
+        let mut x = BigNum::new()?;
+        let mut y = BigNum::new()?;
+        self.key
+            .public_key()
+            .affine_coordinates(self.key.group(), &mut x, &mut y, bn_ctx)?;
+        Ok((x, y))
+    }
 }
 
 impl Key for EcDsaPublicKey {
@@ -128,26 +250,62 @@ impl PubKey for EcDsaPublicKey {
     }
 
     fn verify(&self, data: &[u8], sig: &[u8]) -> Result<bool, Error> {
-        let pkey = PKey::from_ec_key(self.key.clone())?;
-        let mut veri = Verifier::new(MessageDigest::sha1(), &pkey)?;
-        veri.update(data)?;
-        Ok(veri.verify(sig)?)
+        let mut reader = Cursor::new(sig);
+        // The outer blob is string(curve.name()) || string(inner); the name is
+        // already implied by the key, so we only need the inner buffer here.
+        let _sig_type = reader.read_string()?;
+        let inner = reader.read_string()?;
+
+        let mut inner = Cursor::new(inner);
+        let r = BigNum::from_slice(strip_leading_zeros(&inner.read_mpint()?))?;
+        let s = BigNum::from_slice(strip_leading_zeros(&inner.read_mpint()?))?;
+        let ecsig = EcdsaSig::from_private_components(r, s)?;
+
+        let digest = hash(self.curve.digest(), data)?;
+        Ok(ecsig.verify(&digest, &self.key)?)
     }
 }
 
 impl PartialEq for EcDsaPublicKey {
     fn eq(&self, other: &Self) -> bool {
-        let mut bn_ctx = BigNumContext::new().unwrap();
-        //FIXME: rust-openssl doesn't provide a EC_GROUP_cmp() wrapper, so we temporarily use curve type instead.
-        (self.curve == other.curve)
+        let mut bn_ctx = match BigNumContext::new() {
+            Ok(ctx) => ctx,
+            Err(_) => return false,
+        };
+        ec_group_eq(self.key.group(), other.key.group(), &mut bn_ctx)
             && self
                 .key
                 .public_key()
                 .eq(self.key.group(), other.key.public_key(), &mut bn_ctx)
-                .unwrap()
+                .unwrap_or(false)
     }
 }
 
+/// Compare two EC groups without relying on a missing `EC_GROUP_cmp()` wrapper:
+/// prefer the named-curve NID, falling back to the group order and generator for
+/// explicit-parameter encodings. Any OpenSSL error is treated as "not equal".
+fn ec_group_eq(a: &EcGroupRef, b: &EcGroupRef, bn_ctx: &mut BigNumContext) -> bool {
+    match (a.curve_name(), b.curve_name()) {
+        (Some(x), Some(y)) => return x == y,
+        (None, None) => {}
+        _ => return false,
+    }
+
+    let (mut order_a, mut order_b) = match (BigNum::new(), BigNum::new()) {
+        (Ok(a), Ok(b)) => (a, b),
+        _ => return false,
+    };
+    if a.order(&mut order_a, bn_ctx).is_err() || b.order(&mut order_b, bn_ctx).is_err() {
+        return false;
+    }
+    if order_a != order_b {
+        return false;
+    }
+    a.generator()
+        .eq(a, b.generator(), bn_ctx)
+        .unwrap_or(false)
+}
+
 impl fmt::Display for EcDsaPublicKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let body = base64::encode_config(&self.blob().unwrap(), base64::STANDARD);
@@ -161,12 +319,91 @@ pub struct EcDsaKeyPair {
 }
 
 impl EcDsaKeyPair {
+    pub fn generate(curve: EcCurve) -> Result<Self, Error> {
+        let group: EcGroup = curve.try_into()?;
+        Ok(Self {
+            key: EcKey::generate(&group)?,
+            curve,
+        })
+    }
+
+    pub fn from_private_components(curve: EcCurve, private_number: &BigNum) -> Result<Self, Error> {
+        let group: EcGroup = curve.try_into()?;
+        let bn_ctx = BigNumContext::new()?;
+        let mut public_key = EcPoint::new(&group)?;
+        public_key.mul_generator(&group, private_number, &bn_ctx)?;
+        Ok(Self {
+            key: EcKey::from_private_components(&group, private_number, &public_key)?,
+            curve,
+        })
+    }
+
     pub fn clone_public_key(&self) -> Result<EcDsaPublicKey, Error> {
         Ok(EcDsaPublicKey::new(
             self.key.group(),
             self.key.public_key(),
         )?)
     }
+
+    /// Serialize the SSH public-key blob with an explicit SEC1 point form; see
+    /// [`EcDsaPublicKey::blob_with_form`].
+    pub fn blob_with_form(&self, form: PointConversionForm) -> Result<Vec<u8>, Error> {
+        blob_with_form(self.curve, &self.key, form)
+    }
+
+    /// Produce an OpenSSH `SSHSIG`-format detached signature over `data` under
+    /// `namespace`, armored in the `-----BEGIN SSH SIGNATURE-----` PEM wrapper.
+    pub fn sign_sshsig(&self, data: &[u8], namespace: &str) -> Result<String, Error> {
+        let hash_alg = SSHSIG_HASH_ALG;
+        let digest = hash(sshsig_digest(hash_alg.as_bytes())?, data)?;
+        let signed =
+            sshsig_signed_blob(namespace.as_bytes(), hash_alg.as_bytes(), &digest)?;
+        let signature = self.sign(&signed)?;
+
+        let mut buf = Cursor::new(Vec::new());
+        buf.write_all(SSHSIG_MAGIC)?;
+        buf.write_uint32(SSHSIG_VERSION)?;
+        buf.write_string(&self.blob()?)?;
+        buf.write_string(namespace.as_bytes())?;
+        buf.write_string(b"")?;
+        buf.write_string(hash_alg.as_bytes())?;
+        buf.write_string(&signature)?;
+        Ok(armor(&buf.into_inner()))
+    }
+
+    /// Derive the ECDH shared secret with `peer`, returning the X-coordinate of
+    /// the shared point as a fixed-width byte string.
+    pub fn shared_secret(&self, peer: &EcDsaPublicKey) -> Result<Vec<u8>, Error> {
+        if self.curve != peer.curve {
+            return Err(Error::InvalidFormat);
+        }
+        let priv_pkey = PKey::from_ec_key(self.key.clone())?;
+        let peer_pkey = PKey::from_ec_key(peer.key.clone())?;
+        let mut deriver = Deriver::new(&priv_pkey)?;
+        deriver.set_peer(&peer_pkey)?;
+        Ok(deriver.derive_to_vec()?)
+    }
+
+    /// Serialize the key pair as a JSON Web Key, including the private scalar `d`.
+    pub fn to_jwk(&self) -> Result<String, Error> {
+        let mut bn_ctx = BigNumContext::new()?;
+        let (x, y) = self.clone_public_key()?.affine_coordinates(&mut bn_ctx)?;
+        Ok(jwk_json(self.curve, &x, &y, Some(&self.key.private_key().to_owned()?)))
+    }
+
+    /// Reconstruct a key pair from its JSON Web Key representation.
+    pub fn from_jwk(jwk: &str) -> Result<Self, Error> {
+        let curve = jwk_curve(jwk)?;
+        let x = jwk_bignum(jwk, "x")?;
+        let y = jwk_bignum(jwk, "y")?;
+        let d = jwk_bignum(jwk, "d")?;
+        let group: EcGroup = curve.try_into()?;
+        let public = EcKey::from_public_key_affine_coordinates(&group, &x, &y)?;
+        Ok(Self {
+            key: EcKey::from_private_components(&group, &d, public.public_key())?,
+            curve,
+        })
+    }
 }
 
 impl Key for EcDsaKeyPair {
@@ -192,26 +429,151 @@ impl PubKey for EcDsaKeyPair {
 impl PrivKey for EcDsaKeyPair {
     fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
         let pkey = PKey::from_ec_key(self.key.clone())?;
-        let mut sign = Signer::new(MessageDigest::sha1(), &pkey)?;
+        let mut sign = Signer::new(self.curve.digest(), &pkey)?;
         sign.update(data)?;
-        Ok(sign.sign_to_vec()?)
+        let der = sign.sign_to_vec()?;
+
+        // OpenSSL hands us a DER blob; SSH wants string(curve.name()) followed
+        // by string(mpint(r) || mpint(s)).
+        let ecsig = EcdsaSig::from_der(&der)?;
+        let mut inner = Cursor::new(Vec::new());
+        inner.write_mpint(ecsig.r())?;
+        inner.write_mpint(ecsig.s())?;
+
+        let mut buf = Cursor::new(Vec::new());
+        buf.write_string(self.curve.name().as_bytes())?;
+        buf.write_string(&inner.into_inner())?;
+        Ok(buf.into_inner())
+    }
+}
+
+fn sshsig_digest(hash_alg: &[u8]) -> Result<MessageDigest, Error> {
+    match hash_alg {
+        b"sha256" => Ok(MessageDigest::sha256()),
+        b"sha512" => Ok(MessageDigest::sha512()),
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+fn sshsig_signed_blob(namespace: &[u8], hash_alg: &[u8], digest: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut buf = Cursor::new(Vec::new());
+    buf.write_all(SSHSIG_MAGIC)?;
+    buf.write_string(namespace)?;
+    buf.write_string(b"")?;
+    buf.write_string(hash_alg)?;
+    buf.write_string(digest)?;
+    Ok(buf.into_inner())
+}
+
+fn armor(blob: &[u8]) -> String {
+    let encoded = base64::encode_config(blob, base64::STANDARD);
+    let mut out = String::new();
+    out.push_str(SSHSIG_PEM_BEGIN);
+    out.push('\n');
+    for chunk in encoded.as_bytes().chunks(70) {
+        out.push_str(std::str::from_utf8(chunk).unwrap());
+        out.push('\n');
+    }
+    out.push_str(SSHSIG_PEM_END);
+    out.push('\n');
+    out
+}
+
+fn dearmor(armored: &str) -> Result<Vec<u8>, Error> {
+    let mut body = String::new();
+    let mut in_body = false;
+    for line in armored.lines() {
+        let line = line.trim();
+        if line == SSHSIG_PEM_BEGIN {
+            in_body = true;
+        } else if line == SSHSIG_PEM_END {
+            in_body = false;
+        } else if in_body {
+            body.push_str(line);
+        }
+    }
+    base64::decode_config(&body, base64::STANDARD).map_err(|_| Error::InvalidFormat)
+}
+
+fn b64url(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+fn left_pad(bytes: &[u8], size: usize) -> Vec<u8> {
+    if bytes.len() >= size {
+        return bytes.to_vec();
+    }
+    let mut out = vec![0u8; size - bytes.len()];
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn jwk_json(curve: EcCurve, x: &BigNum, y: &BigNum, d: Option<&BigNum>) -> String {
+    let size = curve.coordinate_size();
+    let mut json = format!(
+        "{{\"kty\":\"EC\",\"crv\":\"{}\",\"x\":\"{}\",\"y\":\"{}\"",
+        curve.jwk_name(),
+        b64url(&left_pad(&x.to_vec(), size)),
+        b64url(&left_pad(&y.to_vec(), size)),
+    );
+    if let Some(d) = d {
+        json.push_str(&format!(",\"d\":\"{}\"", b64url(&left_pad(&d.to_vec(), size))));
     }
+    json.push('}');
+    json
+}
+
+/// Extract the string value of a top-level `"field":"value"` pair from the flat,
+/// string-only JSON a JWK is made of. Dependency-light by design, matching the
+/// rest of the crate.
+fn jwk_field<'a>(jwk: &'a str, field: &str) -> Result<&'a str, Error> {
+    let needle = format!("\"{}\"", field);
+    let rest = jwk.get(jwk.find(&needle).ok_or(Error::InvalidFormat)? + needle.len()..)
+        .ok_or(Error::InvalidFormat)?;
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix(':').ok_or(Error::InvalidFormat)?.trim_start();
+    let rest = rest.strip_prefix('"').ok_or(Error::InvalidFormat)?;
+    let end = rest.find('"').ok_or(Error::InvalidFormat)?;
+    Ok(&rest[..end])
+}
+
+fn jwk_curve(jwk: &str) -> Result<EcCurve, Error> {
+    EcCurve::from_jwk_name(jwk_field(jwk, "crv")?)
+}
+
+fn jwk_bignum(jwk: &str, field: &str) -> Result<BigNum, Error> {
+    let bytes = base64::decode_config(jwk_field(jwk, field)?, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| Error::InvalidFormat)?;
+    Ok(BigNum::from_slice(&bytes)?)
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let mut start = 0;
+    while start + 1 < bytes.len() && bytes[start] == 0 {
+        start += 1;
+    }
+    &bytes[start..]
 }
 
 fn eckey_blob<T: HasPublic + HasParams>(
     curve: EcCurve,
     key: &EcKeyRef<T>,
+) -> Result<Vec<u8>, Error> {
+    // SSH always expects the uncompressed point encoding on the wire.
+    blob_with_form(curve, key, PointConversionForm::UNCOMPRESSED)
+}
+
+fn blob_with_form<T: HasPublic + HasParams>(
+    curve: EcCurve,
+    key: &EcKeyRef<T>,
+    form: PointConversionForm,
 ) -> Result<Vec<u8>, Error> {
     let mut buf = Cursor::new(Vec::new());
     let mut bn_ctx = BigNumContext::new()?;
 
     buf.write_utf8(curve.name())?;
     buf.write_utf8(curve.ident())?;
-    buf.write_string(&key.public_key().to_bytes(
-        key.group(),
-        PointConversionForm::UNCOMPRESSED,
-        &mut bn_ctx,
-    )?)?;
+    buf.write_string(&key.public_key().to_bytes(key.group(), form, &mut bn_ctx)?)?;
 
     Ok(buf.into_inner())
 }
@@ -247,9 +609,101 @@ mod test {
         assert_eq!(key.to_string(), String::from(pub_str));
     }
 
+    #[test]
+    fn sshsig_roundtrip_all_curves() {
+        for curve in ALL_CURVES.iter().copied() {
+            let pair = EcDsaKeyPair::generate(curve).unwrap();
+            let pubkey = pair.clone_public_key().unwrap();
+            let data = b"file contents to be signed";
+            let armored = pair.sign_sshsig(data, "file").unwrap();
+            assert!(armored.starts_with(SSHSIG_PEM_BEGIN));
+            assert!(pubkey.verify_sshsig(data, "file", &armored).unwrap());
+            // Wrong namespace or tampered data must not verify.
+            assert!(!pubkey.verify_sshsig(data, "email", &armored).unwrap());
+            assert!(!pubkey.verify_sshsig(b"tampered", "file", &armored).unwrap());
+        }
+    }
+
+    #[test]
+    fn ecdsa_shared_secret_agrees() {
+        let alice = EcDsaKeyPair::generate(EcCurve::Nistp256).unwrap();
+        let bob = EcDsaKeyPair::generate(EcCurve::Nistp256).unwrap();
+        let ab = alice.shared_secret(&bob.clone_public_key().unwrap()).unwrap();
+        let ba = bob.shared_secret(&alice.clone_public_key().unwrap()).unwrap();
+        assert!(!ab.is_empty());
+        assert_eq!(ab, ba);
+    }
+
+    #[test]
+    fn ecdsa_shared_secret_rejects_curve_mismatch() {
+        let alice = EcDsaKeyPair::generate(EcCurve::Nistp256).unwrap();
+        let bob = EcDsaKeyPair::generate(EcCurve::Nistp384).unwrap();
+        assert!(alice.shared_secret(&bob.clone_public_key().unwrap()).is_err());
+    }
+
     #[test]
     fn ecdsa_publickey_size() {
         let key = get_test_pubkey().unwrap();
         assert_eq!(key.size(), 256);
     }
+
+    const ALL_CURVES: [EcCurve; 3] = [EcCurve::Nistp256, EcCurve::Nistp384, EcCurve::Nistp521];
+
+    #[test]
+    fn ecdsa_sign_verify_roundtrip() {
+        for curve in ALL_CURVES.iter().copied() {
+            let pair = EcDsaKeyPair::generate(curve).unwrap();
+            let pubkey = pair.clone_public_key().unwrap();
+            let data = b"osshkeys sign/verify round-trip";
+            let sig = pair.sign(data).unwrap();
+            assert!(pubkey.verify(data, &sig).unwrap());
+            assert!(!pubkey.verify(b"tampered", &sig).unwrap());
+        }
+    }
+
+    #[test]
+    fn ecdsa_jwk_public_fixed_vector() {
+        // Coordinates lifted from the uncompressed pub_key vector (0x04 || x || y).
+        let jwk = format!(
+            "{{\"kty\":\"EC\",\"crv\":\"P-256\",\"x\":\"{}\",\"y\":\"{}\"}}",
+            b64url(&pub_key[1..33]),
+            b64url(&pub_key[33..65]),
+        );
+        let key = EcDsaPublicKey::from_jwk(&jwk).unwrap();
+        assert_eq!(key, get_test_pubkey().unwrap());
+        // Re-export then re-import must be stable.
+        let reparsed = EcDsaPublicKey::from_jwk(&key.to_jwk().unwrap()).unwrap();
+        assert_eq!(reparsed, key);
+    }
+
+    #[test]
+    fn ecdsa_jwk_private_roundtrip() {
+        for curve in ALL_CURVES.iter().copied() {
+            let pair = EcDsaKeyPair::generate(curve).unwrap();
+            let restored = EcDsaKeyPair::from_jwk(&pair.to_jwk().unwrap()).unwrap();
+            assert_eq!(
+                restored.clone_public_key().unwrap(),
+                pair.clone_public_key().unwrap()
+            );
+            // The restored private scalar must still sign verifiably.
+            let data = b"jwk private round-trip";
+            let sig = restored.sign(data).unwrap();
+            assert!(pair.clone_public_key().unwrap().verify(data, &sig).unwrap());
+        }
+    }
+
+    #[test]
+    fn ecdsa_publickey_compressed_roundtrip() {
+        let key = get_test_pubkey().unwrap();
+        let mut bn_ctx = BigNumContext::new().unwrap();
+        let compressed = key
+            .key
+            .public_key()
+            .to_bytes(key.key.group(), PointConversionForm::COMPRESSED, &mut bn_ctx)
+            .unwrap();
+        assert!(compressed[0] == 0x02 || compressed[0] == 0x03);
+
+        let parsed = EcDsaPublicKey::from_point_bytes(EcCurve::Nistp256, &compressed).unwrap();
+        assert_eq!(parsed, key);
+    }
 }
\ No newline at end of file